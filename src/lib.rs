@@ -5,10 +5,14 @@
 ///
 pub use console::Key;
 use console::{style, Term};
-use dialoguer::{theme::ColorfulTheme, Confirmation, Input, Select};
+use dialoguer::{
+    theme::{ColorfulTheme, Theme},
+    Confirmation, Input as DInput, Select,
+};
 use std::fmt;
 use std::io;
 use std::io::Read;
+use std::str::FromStr;
 use std::sync;
 
 pub fn _redir_stdout() -> (sync::mpsc::Sender<bool>, sync::mpsc::Receiver<String>) {
@@ -121,16 +125,119 @@ pub fn _unredir_stdout(arg: (sync::mpsc::Sender<bool>, sync::mpsc::Receiver<Stri
 ///
 /// <Parent Menu Displayed>
 /// ```
-pub struct TrackedTerm(Term, usize, usize);
+pub struct TrackedTerm {
+    term: Term,
+    /// Rows the tracked Display section currently occupies. An `Arc` so a
+    /// background `LogPoller` thread can read it without needing a lock on
+    /// the whole `TrackedTerm`.
+    lines: sync::Arc<sync::atomic::AtomicUsize>,
+    cursor: usize,
+    log: Option<sync::Arc<sync::Mutex<sync::mpsc::Receiver<String>>>>,
+    /// Set for the duration of a `Select`/`Confirmation` prompt, which
+    /// renders its item list directly against the raw `Term` rather than
+    /// through `write_line`, so `lines` doesn't cover it. The poller checks
+    /// this before draining so it never inserts a log line into the middle
+    /// of a prompt it has no row count for.
+    rendering: sync::Arc<sync::atomic::AtomicBool>,
+    alt_screen: bool,
+    /// The theme every `Select`/`Confirmation` in this menu tree renders
+    /// with, set once by the outermost `Directory::run()` and read by every
+    /// nested `Directory::exec` that shares this `TrackedTerm`.
+    theme: Box<dyn Theme>,
+}
 
 impl TrackedTerm {
     /// Get a new TrackedTerm for stdout.
     pub fn stdout() -> Self {
-        Self(Term::stdout(), 0, 0)
+        Self::new(Term::stdout())
+    }
+    /// Wrap an arbitrary `console::Term`, e.g. one pointed at stderr, a
+    /// buffered stream, or a `Term::read_write_pair`.
+    pub fn new(term: Term) -> Self {
+        Self {
+            term,
+            lines: sync::Arc::new(sync::atomic::AtomicUsize::new(0)),
+            cursor: 0,
+            log: None,
+            rendering: sync::Arc::new(sync::atomic::AtomicBool::new(false)),
+            alt_screen: false,
+            theme: Box::new(ColorfulTheme::default()),
+        }
+    }
+    /// Set the theme every `Select`/`Confirmation` rendered through this
+    /// `TrackedTerm` (including by nested `Directory`s) uses.
+    fn set_theme(&mut self, theme: Box<dyn Theme>) {
+        self.theme = theme;
+    }
+    /// Get the theme currently configured for this `TrackedTerm`.
+    pub(crate) fn theme(&self) -> &dyn Theme {
+        self.theme.as_ref()
+    }
+    /// Mark that a `Select`/`Confirmation` prompt is about to render
+    /// directly against the raw `Term`, so the log poller leaves the
+    /// screen alone until [`end_render`](#method.end_render) is called.
+    fn begin_render(&self) {
+        self.rendering.store(true, sync::atomic::Ordering::SeqCst);
+    }
+    /// Mark that the prompt started by [`begin_render`](#method.begin_render)
+    /// has finished drawing (or been torn down by `reset()`), so the log
+    /// poller may resume draining.
+    fn end_render(&self) {
+        self.rendering.store(false, sync::atomic::Ordering::SeqCst);
+    }
+    /// Mark whether this TrackedTerm is rendering inside an alternate
+    /// screen buffer, in which case the incremental clearing `reset` and
+    /// `clear_last_lines` normally do is skipped: the whole buffer is
+    /// discarded when the alternate screen is left, so there's nothing to
+    /// clean up incrementally.
+    fn set_alt_screen(&mut self, enabled: bool) {
+        self.alt_screen = enabled;
+    }
+    /// Wire up a log channel and spawn a [`LogPoller`] that periodically
+    /// drains it into the Log section on its own, so lines pushed via a
+    /// `LogHandle` show up even while this `TrackedTerm` is otherwise idle
+    /// (e.g. blocked inside a `Select` prompt). The returned poller must be
+    /// stopped once this `TrackedTerm` stops being used.
+    fn spawn_log_poller(&mut self, rx: sync::mpsc::Receiver<String>) -> LogPoller {
+        let rx = sync::Arc::new(sync::Mutex::new(rx));
+        self.log = Some(rx.clone());
+        let term = self.term.clone();
+        let lines = self.lines.clone();
+        let rendering = self.rendering.clone();
+        let stop = sync::Arc::new(sync::atomic::AtomicBool::new(false));
+        let poller_stop = stop.clone();
+        let handle = std::thread::spawn(move || {
+            while !poller_stop.load(sync::atomic::Ordering::SeqCst) {
+                std::thread::sleep(std::time::Duration::from_millis(150));
+                if rendering.load(sync::atomic::Ordering::SeqCst) {
+                    continue;
+                }
+                drain_pending_log(&term, &lines, &rx);
+            }
+        });
+        LogPoller { stop, handle }
+    }
+    /// Drain any log lines that have piled up since the last poll and
+    /// insert them above the currently tracked Display section, so they
+    /// join the scrollback of the Log section without disturbing what's on
+    /// screen below them.
+    ///
+    /// This is also done on a timer by [`spawn_log_poller`](#method.spawn_log_poller),
+    /// so lines show up while the menu is idle, not just on redraw. Both
+    /// paths skip draining while [`begin_render`](#method.begin_render) has
+    /// marked a `Select`/`Confirmation` prompt as currently on screen, since
+    /// `lines` doesn't cover rows that prompt draws itself.
+    fn drain_log(&self) {
+        if self.rendering.load(sync::atomic::Ordering::SeqCst) {
+            return;
+        }
+        if let Some(rx) = self.log.as_ref() {
+            drain_pending_log(&self.term, &self.lines, rx);
+        }
     }
     /// Get a reference to the underlying console::Term
     pub fn unwrap(&self) -> &Term {
-        &self.0
+        &self.term
     }
     /// Writes an empty line to the screen, incrementing the line count.
     pub fn line_break(&mut self) {
@@ -140,68 +247,180 @@ impl TrackedTerm {
     ///
     /// Writes the given string to stdout and increments the line count.
     /// This method also handles multi-line strings.
+    ///
+    /// A logical line that's wider than the terminal wraps onto several
+    /// physical rows, so each one is counted by its true row count rather
+    /// than as a single row. This keeps `reset()`'s `clear_last_lines` call
+    /// accurate regardless of the window size.
     pub fn write_line(&mut self, s: &str) {
+        let width = self.term.size_checked().map(|(_, cols)| cols as usize);
         for line in s.lines() {
-            self.0.write_line(line).ok();
-            self.1 += 1;
+            self.term.write_line(line).ok();
+            self.lines
+                .fetch_add(Self::rows_for_line(line, width), sync::atomic::Ordering::SeqCst);
+        }
+    }
+    /// Number of physical terminal rows a logical line occupies.
+    ///
+    /// `width` is the terminal's column count, or `None` when it can't be
+    /// determined (e.g. output isn't a terminal), in which case the line is
+    /// treated as a single, unbounded row. An empty line always counts as
+    /// one row.
+    fn rows_for_line(line: &str, width: Option<usize>) -> usize {
+        match width {
+            Some(width) if width > 0 => {
+                let display_width = console::measure_text_width(line);
+                std::cmp::max(1, display_width.div_ceil(width))
+            }
+            _ => 1,
         }
     }
     /// Clears the written lines and resets the line count.
+    ///
+    /// In alternate-screen mode, the incremental `clear_last_lines` dance
+    /// is skipped in favor of clearing the whole (alternate) screen, since
+    /// there's no surrounding shell scrollback above it to preserve.
     pub fn reset(&mut self) {
-        if self.2 > 0 {
-            self.move_cursor_down(self.2);
+        if self.alt_screen {
+            self.term.clear_screen().ok();
+            self.lines.store(0, sync::atomic::Ordering::SeqCst);
+            self.cursor = 0;
+            return;
+        }
+        if self.cursor > 0 {
+            self.move_cursor_down(self.cursor);
         }
-        self.0.clear_last_lines(self.1 + 1).ok();
-        self.1 = 0;
+        let lines = self.lines.load(sync::atomic::Ordering::SeqCst);
+        self.term.clear_last_lines(lines + 1).ok();
+        self.lines.store(0, sync::atomic::Ordering::SeqCst);
     }
     /// Clear the last line without modifying the line count.
     ///
     /// This method should only be used when a line was written to the
     /// screen without going through this wrapper.
     pub fn force_clear_line(&mut self) {
-        self.0.clear_last_lines(1).ok();
+        self.term.clear_last_lines(1).ok();
     }
-    /// Works just like Term.clear_last_lines, but also tracks the reduced line count.
+    /// Works just like Term.clear_last_lines, but also tracks the reduced
+    /// line count. In alternate-screen mode, the clear itself is skipped
+    /// (see [`reset`](#method.reset)) but the line count is still tracked.
     pub fn clear_last_lines(&mut self, n: usize) {
-        if n > (self.1 - self.2) {
-            self.0.clear_last_lines(self.1 - self.2).ok();
-            self.2 += self.1 - self.2;
-        } else {
-            self.0.clear_last_lines(n).ok();
-            self.2 += n;
+        let lines = self.lines.load(sync::atomic::Ordering::SeqCst);
+        let n = if n > (lines - self.cursor) { lines - self.cursor } else { n };
+        if !self.alt_screen {
+            self.term.clear_last_lines(n).ok();
         }
+        self.cursor += n;
     }
     /// Works just like Term.move_cursor_up, but also tracks that movement.
     ///
     /// For more information, see [move_cursor_down](#method.move_cursor_down).
     pub fn move_cursor_up(&mut self, n: usize) {
-        self.2 += n;
-        self.0.move_cursor_up(n).ok();
+        self.cursor += n;
+        self.term.move_cursor_up(n).ok();
     }
     /// Works just like Term.move_cursor_down, but also tracks that movement.
     ///
-    /// Cursor position is tracked with TrackedTerm.2.
-    /// Every movement upwards increments self.2 by 1,
-    /// and every movement downwards decrements self.2.
-    /// Once self.2 reaches zero, move_cursor_down is
+    /// Cursor position is tracked with TrackedTerm's cursor field.
+    /// Every movement upwards increments it by 1,
+    /// and every movement downwards decrements it.
+    /// Once it reaches zero, move_cursor_down is
     /// basically the same as line_break, so further movement
-    /// downwards instead increments self.1
+    /// downwards instead increments the tracked line count.
     pub fn move_cursor_down(&mut self, n: usize) {
-        match self.2.checked_sub(n) {
-            Some(r) => self.2 = r,
+        match self.cursor.checked_sub(n) {
+            Some(r) => self.cursor = r,
             None => {
-                self.1 += match n.checked_sub(self.2) {
+                let extra = match n.checked_sub(self.cursor) {
                     Some(r) => r,
                     None => panic!("This can't happen"),
                 };
-                self.2 = 0;
+                self.lines.fetch_add(extra, sync::atomic::Ordering::SeqCst);
+                self.cursor = 0;
             }
         };
-        self.0.move_cursor_down(n).ok();
+        self.term.move_cursor_down(n).ok();
     }
     /// Convienience function to access the underlying Term.read_key method.
     pub fn read_key(&self) -> Key {
-        self.0.read_key().unwrap()
+        self.term.read_key().unwrap()
+    }
+}
+
+/// Drain whatever log lines are waiting on `rx` and insert them above the
+/// `lines`-row Display section tracked by a `TrackedTerm`, shared between
+/// `TrackedTerm::drain_log` (called once per render) and `LogPoller`
+/// (called on a timer) so both paths stay in sync.
+fn drain_pending_log(
+    term: &Term,
+    lines: &sync::atomic::AtomicUsize,
+    rx: &sync::Mutex<sync::mpsc::Receiver<String>>,
+) {
+    let pending: Vec<String> = match rx.lock() {
+        Ok(rx) => rx.try_iter().collect(),
+        Err(_) => return,
+    };
+    if pending.is_empty() {
+        return;
+    }
+    let offset = lines.load(sync::atomic::Ordering::SeqCst);
+    if offset > 0 {
+        term.move_cursor_up(offset).ok();
+    }
+    for line in &pending {
+        term.write_str("\x1b[L").ok();
+        term.write_line(line).ok();
+    }
+    if offset > 0 {
+        term.move_cursor_down(offset).ok();
+    }
+}
+
+/// A background poller spawned by `TrackedTerm::spawn_log_poller` that
+/// periodically drains a `Directory`'s log channel so lines pushed via a
+/// `LogHandle` appear even while the menu is just sitting idle at a prompt.
+/// Stop it with [`stop`](#method.stop) once the `TrackedTerm` it was
+/// spawned for is done being used.
+struct LogPoller {
+    stop: sync::Arc<sync::atomic::AtomicBool>,
+    handle: std::thread::JoinHandle<()>,
+}
+
+impl LogPoller {
+    fn stop(self) {
+        self.stop.store(true, sync::atomic::Ordering::SeqCst);
+        self.handle.join().ok();
+    }
+}
+
+/// RAII guard that switches a terminal into its alternate screen buffer for
+/// as long as it's alive, restoring the original screen (with its prior
+/// scrollback intact) on drop.
+///
+/// Creating it emits the enter-alternate-screen sequence; dropping it
+/// (including via an unwinding panic, or an early `break` out of
+/// `Directory`'s exit-confirmation path) emits the leave sequence, so the
+/// terminal is never left stuck on the alternate buffer.
+struct AltScreenGuard(Term);
+
+impl AltScreenGuard {
+    fn enter(term: &Term) -> Self {
+        term.write_str("\x1b[?1049h").ok();
+        // Buffered terminals (e.g. Term::buffered_stdout()) only append to
+        // an internal buffer on write_str; without an explicit flush the
+        // escape sequence can sit there indefinitely.
+        term.flush().ok();
+        Self(term.clone())
+    }
+}
+
+impl Drop for AltScreenGuard {
+    fn drop(&mut self) {
+        self.0.write_str("\x1b[?1049l").ok();
+        // Same reasoning as `enter`: a buffered terminal won't push this
+        // out on its own, and leaving it unflushed here means the real
+        // terminal never sees the leave-alt-screen sequence.
+        self.0.flush().ok();
     }
 }
 
@@ -277,6 +496,10 @@ pub struct Directory {
     items: Vec<Box<dyn MenuItem>>,
     selected: usize,
     exit_confirmation: Option<String>,
+    log: Option<(sync::mpsc::Sender<String>, sync::mpsc::Receiver<String>)>,
+    term: Option<Term>,
+    theme: Option<Box<dyn Theme>>,
+    alternate_screen: bool,
 }
 
 impl Directory {
@@ -287,6 +510,10 @@ impl Directory {
             items: Vec::new(),
             selected: 0,
             exit_confirmation: None,
+            log: None,
+            term: None,
+            theme: None,
+            alternate_screen: false,
         }
     }
     /// Set a custom prompt to display when the menu executes
@@ -305,9 +532,82 @@ impl Directory {
     pub fn confirmation(&mut self, prompt: &str) {
         self.exit_confirmation = Some(String::from(prompt));
     }
+    /// Choose the `console::Term` this menu (and every prompt nested
+    /// inside it) renders to, instead of the default `Term::stdout()`.
+    ///
+    /// Accepts anything `console::Term` can be built from: `Term::stderr()`,
+    /// a buffered variant like `Term::buffered_stderr()`, or an arbitrary
+    /// `Term::read_write_pair`.
+    ///
+    /// Only takes effect on the `Directory` whose `run()` you call: that's
+    /// the one that creates the `TrackedTerm` the whole nested menu tree
+    /// renders through, so setting this on a submenu that's never `run()`
+    /// itself (every submenu built by `diralogue!`'s nested `[...]` arm) has
+    /// no effect.
+    pub fn target(&mut self, term: Term) {
+        self.term = Some(term);
+    }
+    /// Use a custom dialoguer theme for this menu and every prompt nested
+    /// inside it, instead of the default `ColorfulTheme`.
+    ///
+    /// Like [`target`](#method.target), only takes effect on the `Directory`
+    /// whose `run()` you call: the theme is resolved once in `run()` and
+    /// threaded through the shared `TrackedTerm`, so every nested menu
+    /// renders with the same one.
+    pub fn theme(&mut self, theme: Box<dyn Theme>) {
+        self.theme = Some(theme);
+    }
+    /// Run the menu in a full-screen alternate screen buffer, so its
+    /// Log/Display output doesn't scroll into the user's shell history.
+    ///
+    /// The original screen (with its scrollback intact) is restored as
+    /// soon as `run()` returns, even on panic.
+    pub fn alternate_screen(&mut self, enabled: bool) {
+        self.alternate_screen = enabled;
+    }
+    /// Get a handle that lets other threads push lines into the Log
+    /// section while this directory's menu is still being displayed.
+    ///
+    /// Must be called before `run()`, since that's when the channel is
+    /// wired into the `TrackedTerm` the menu renders with.
+    pub fn log_handle(&mut self) -> LogHandle {
+        if self.log.is_none() {
+            self.log = Some(sync::mpsc::channel());
+        }
+        LogHandle(self.log.as_ref().unwrap().0.clone())
+    }
     pub fn run(&mut self) {
-        println!("------Log------");
-        self.exec(&mut TrackedTerm::stdout());
+        let raw_term = self.term.take().unwrap_or_else(Term::stdout);
+        let _alt_screen_guard = if self.alternate_screen {
+            Some(AltScreenGuard::enter(&raw_term))
+        } else {
+            None
+        };
+        raw_term.write_line("------Log------").ok();
+        let mut term = TrackedTerm::new(raw_term);
+        term.set_alt_screen(self.alternate_screen);
+        if let Some(theme) = self.theme.take() {
+            term.set_theme(theme);
+        }
+        let log_poller = self.log.take().map(|(_, rx)| term.spawn_log_poller(rx));
+        self.exec(&mut term);
+        if let Some(poller) = log_poller {
+            poller.stop();
+        }
+    }
+}
+
+/// A cloneable, `Send` handle for pushing lines into a `Directory`'s Log
+/// section from another thread while its menu is still interactive.
+///
+/// Obtained from [`Directory::log_handle`] before calling `run()`.
+#[derive(Clone)]
+pub struct LogHandle(sync::mpsc::Sender<String>);
+
+impl LogHandle {
+    /// Enqueue a line to be printed to the Log section.
+    pub fn println(&self, line: &str) {
+        self.0.send(String::from(line)).ok();
     }
 }
 
@@ -317,28 +617,39 @@ impl MenuItem for Directory {
     }
     fn exec(&mut self, term: &mut TrackedTerm) {
         loop {
+            term.drain_log();
             term.line_break();
             term.line_break();
             let items: Vec<&str> = self.items.iter().map(|mi| mi.name()).collect();
-            let rv = Select::with_theme(&ColorfulTheme::default())
+            term.begin_render();
+            let rv = Select::with_theme(term.theme())
                 .with_prompt(&self.prompt)
                 .items(&items)
                 .default(self.selected)
                 .clear(true)
-                .interact_opt()
+                .interact_on_opt(term.unwrap())
                 .unwrap();
+            term.end_render();
             // term.force_clear_line();
             match rv {
                 Some(v) => {
                     term.reset();
                     self.selected = v;
-                    println!("{}: {}", self.prompt, style(items[v]).green());
+                    term.unwrap()
+                        .write_line(&format!("{}: {}", self.prompt, style(items[v]).green()))
+                        .ok();
                     self.items[v].exec(term);
                 }
                 None => {
                     term.reset();
                     if let Some(ec) = self.exit_confirmation.as_ref() {
-                        if Confirmation::new().with_text(&ec).interact().unwrap() {
+                        term.begin_render();
+                        let confirmed = Confirmation::with_theme(term.theme())
+                            .with_text(&ec)
+                            .interact_on(term.unwrap())
+                            .unwrap();
+                        term.end_render();
+                        if confirmed {
                             break;
                         }
                     } else {
@@ -443,6 +754,122 @@ impl MenuItem for Toggle {
     }
 }
 
+/// A text-input menuitem, backed by `dialoguer::Input`.
+///
+/// Activating this item prompts the user for a line of text, parses it
+/// into `T`, and stores the result so the parent `Directory` (or any other
+/// code holding onto the item) can read it back via [`value`](#method.value).
+/// Its `name()` mirrors `Toggle`'s, showing the captured value once one
+/// exists (e.g. `"Your name: Alice"`).
+///
+/// Note: this dialoguer line's `Input` doesn't offer completion, only
+/// validation, so there's no `completion_with` here.
+pub struct Input<T>
+where
+    T: Clone + fmt::Display + FromStr,
+    T::Err: fmt::Display + fmt::Debug,
+{
+    title: String,
+    content: String,
+    prompt: Option<String>,
+    default: Option<T>,
+    allow_empty: bool,
+    validator: Option<std::rc::Rc<dyn Fn(&T) -> Result<(), String>>>,
+    value: Option<T>,
+}
+
+impl<T> Input<T>
+where
+    T: Clone + fmt::Display + FromStr,
+    T::Err: fmt::Display + fmt::Debug,
+{
+    pub fn new(text: &str) -> Input<T> {
+        Input {
+            title: String::from(text),
+            content: String::from(text),
+            prompt: None,
+            default: None,
+            allow_empty: false,
+            validator: None,
+            value: None,
+        }
+    }
+    /// Set the prompt shown when the item is activated.
+    ///
+    /// Defaults to the item's title if not set.
+    pub fn with_prompt(mut self, prompt: &str) -> Self {
+        self.prompt = Some(String::from(prompt));
+        self
+    }
+    /// Pre-fill a default value, used if the user submits an empty line.
+    pub fn default(mut self, default: T) -> Self {
+        self.default = Some(default);
+        self
+    }
+    /// Whether an empty line is accepted as a valid (empty) value.
+    pub fn allow_empty(mut self, allow_empty: bool) -> Self {
+        self.allow_empty = allow_empty;
+        self
+    }
+    /// Validate the parsed value before it's accepted, returning an error
+    /// message to redisplay the prompt with if it's invalid.
+    pub fn validate_with<F>(mut self, validator: F) -> Self
+    where
+        F: Fn(&T) -> Result<(), String> + 'static,
+    {
+        self.validator = Some(std::rc::Rc::new(validator));
+        self
+    }
+    /// The value captured the last time this item was activated, if any.
+    pub fn value(&self) -> Option<&T> {
+        self.value.as_ref()
+    }
+    fn update_content(&mut self) {
+        self.content = match &self.value {
+            Some(v) => format!("{}: {}", self.title, v),
+            None => self.title.clone(),
+        };
+    }
+}
+
+impl<T> MenuItem for Input<T>
+where
+    T: Clone + fmt::Display + FromStr,
+    T::Err: fmt::Display + fmt::Debug,
+{
+    fn name(&self) -> &str {
+        &self.content
+    }
+    fn exec(&mut self, term: &mut TrackedTerm) {
+        let mut input = DInput::with_theme(term.theme());
+        input.with_prompt(self.prompt.as_deref().unwrap_or(&self.title));
+        input.allow_empty(self.allow_empty);
+        if let Some(d) = self.default.as_ref() {
+            input.default(d.clone());
+        }
+        if let Some(validator) = self.validator.clone() {
+            // dialoguer validates against the raw &str, before it's parsed
+            // into T, so parsing happens here and any parse failure is
+            // surfaced the same way a validation failure would be. The
+            // validator is Rc'd (rather than moved out of self) since this
+            // item can be activated more than once in the same menu.
+            input.validate_with(move |s: &str| -> Result<(), String> {
+                match s.parse::<T>() {
+                    Ok(v) => validator(&v),
+                    Err(e) => Err(e.to_string()),
+                }
+            });
+        }
+        if let Ok(value) = input.interact_on(term.unwrap()) {
+            self.value = Some(value);
+            self.update_content();
+        }
+    }
+    fn is_enabled(&self) -> bool {
+        true
+    }
+}
+
 #[allow(unused_macros)]
 #[macro_export]
 macro_rules! diralogue {
@@ -477,6 +904,14 @@ macro_rules! diralogue {
         diralogue!(item $outer $id, $($rest)*);
     }};
 
+    (item $outer:ident $id:expr, $title:expr => input!($ty:ty); $($rest:tt)*) => {{
+        let mut parent = $outer.pop().unwrap();
+        let item = diraloguer::Input::<$ty>::new($title);
+        parent.item(Box::new(item));
+        $outer.push(parent);
+        diralogue!(item $outer $id, $($rest)*);
+    }};
+
     (item $outer:ident $id:expr, $title:expr => $inner:expr; $($rest:tt)*) => {{
         let mut parent = $outer.pop().unwrap();
         let item = diraloguer::Function {
@@ -496,4 +931,30 @@ mod tests {
     use super::*;
     #[test]
     fn it_works() {}
+
+    #[test]
+    fn rows_for_line_fits_on_one_row() {
+        assert_eq!(TrackedTerm::rows_for_line("hello", Some(80)), 1);
+    }
+
+    #[test]
+    fn rows_for_line_wraps_across_several_rows() {
+        assert_eq!(TrackedTerm::rows_for_line(&"x".repeat(25), Some(10)), 3);
+    }
+
+    #[test]
+    fn rows_for_line_empty_line_is_one_row() {
+        assert_eq!(TrackedTerm::rows_for_line("", Some(80)), 1);
+    }
+
+    #[test]
+    fn rows_for_line_unknown_width_is_one_row() {
+        assert_eq!(TrackedTerm::rows_for_line(&"x".repeat(200), None), 1);
+    }
+
+    #[test]
+    fn rows_for_line_strips_ansi_before_measuring() {
+        let styled = format!("{}", style("hello").green());
+        assert_eq!(TrackedTerm::rows_for_line(&styled, Some(80)), 1);
+    }
 }